@@ -8,7 +8,14 @@
 // serde = { version = "1.0", features = ["derive"] }
 // serde_json = "1.0"
 // dirs = "5.0"
-// 
+// sysinfo = "0.30"
+// globset = "0.4"
+// rfd = "0.13"
+// crossbeam-channel = "0.5"
+// trash = "3"
+// clap = { version = "4", features = ["derive"] }
+// blake3 = "1"
+//
 // [target.'cfg(windows)'.dependencies]
 // winapi = { version = "0.3", features = ["winuser", "windef", "shellapi", "objbase", "combaseapi"] }
 
@@ -19,9 +26,10 @@ use eframe::egui;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use walkdir::WalkDir;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -48,6 +56,30 @@ struct FlBackupCleaner {
     settings_open: bool,  // Track if settings modal is open
     available_drives: Vec<DriveInfo>,  // List of available drives
     settings: Settings,  // User settings
+    stop_sender: Option<crossbeam_channel::Sender<()>>,  // Sends a stop signal to running scan workers
+    stop_flag: Option<Arc<AtomicBool>>,  // Cooperative cancellation token shared with scan workers
+    scan_stage: (usize, usize),  // (current_stage, max_stage) for two-phase progress
+    include_input: String,  // Settings modal: pending included-directory entry
+    exclude_input: String,  // Settings modal: pending excluded-item entry
+    glob_input: String,  // Settings modal: pending exclude-glob entry
+    backup_name_input: String,  // Settings modal: pending backup-folder name entry
+    ext_input: String,  // Settings modal: pending project-extension entry
+    project_filter: String,  // Live filter over the found-projects list
+    project_sort: ProjectSort,  // Sort order for the found-projects list
+}
+
+// Sort order for the Project Details list.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ProjectSort {
+    TotalSize,   // Largest total backup size first
+    BackupCount, // Most backups first
+    MostRecent,  // Newest backup timestamp first
+}
+
+impl Default for ProjectSort {
+    fn default() -> Self {
+        ProjectSort::TotalSize
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -62,6 +94,36 @@ impl Default for Theme {
     }
 }
 
+// How the cleaner disposes of old backups.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum DeleteMethod {
+    DryRun,          // Tally what would be removed without touching disk
+    MoveToTrash,     // Move files to the OS recycle bin (recoverable)
+    PermanentDelete, // Permanently remove files with fs::remove_file
+}
+
+impl Default for DeleteMethod {
+    fn default() -> Self {
+        // Default to the recoverable option: project files are irreplaceable.
+        DeleteMethod::MoveToTrash
+    }
+}
+
+// Which backups to keep per project when cleaning.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum RetentionPolicy {
+    KeepLatest,          // Keep only the single newest backup (historical default)
+    KeepN(usize),        // Keep the N most recent backups
+    KeepNewerThan(u32),  // Keep backups modified within this many days of now
+    MaxTotalMb(u64),     // Keep the newest backups fitting within this size cap (MB)
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        RetentionPolicy::KeepLatest
+    }
+}
+
 // Settings structure to store user preferences
 #[derive(Clone, Serialize, Deserialize)]
 struct Settings {
@@ -70,6 +132,37 @@ struct Settings {
     max_scan_threads: usize,  // Maximum number of scanning threads
     scan_depth: usize,  // Maximum directory depth to scan
     auto_clean: bool,   // Automatically clean after scan
+    #[serde(default)]
+    delete_method: DeleteMethod,  // How old backups are disposed of
+    #[serde(default)]
+    delete_broken: bool,  // Remove corrupt .flp backups regardless of the keep-latest rule
+    #[serde(default)]
+    included_directories: Vec<PathBuf>,  // Roots to scan instead of whole drives, when set
+    #[serde(default)]
+    excluded_items: Vec<String>,  // Substring patterns; matching paths are pruned
+    #[serde(default)]
+    excluded_directories: Vec<PathBuf>,  // Whole subtrees to skip during the walk
+    #[serde(default)]
+    retention: RetentionPolicy,  // How many backups to keep per project
+    #[serde(default = "default_exclude_globs")]
+    exclude_globs: Vec<String>,  // Glob patterns pruned during the walk
+    #[serde(default = "default_backup_folder_names")]
+    backup_folder_names: Vec<String>,  // Folder names recognised as backup folders
+    #[serde(default = "default_project_extensions")]
+    project_extensions: Vec<String>,  // File extensions recognised as projects
+    #[serde(default)]
+    custom_scan_paths: Vec<PathBuf>,  // Extra folders to scan alongside selected drives
+}
+
+// Defaults that preserve the previously hardcoded matching behaviour.
+fn default_exclude_globs() -> Vec<String> {
+    Vec::new()
+}
+fn default_backup_folder_names() -> Vec<String> {
+    vec!["Backup".to_string()]
+}
+fn default_project_extensions() -> Vec<String> {
+    vec!["flp".to_string()]
 }
 
 impl Default for Settings {
@@ -80,6 +173,16 @@ impl Default for Settings {
             max_scan_threads: 4,
             scan_depth: 15,
             auto_clean: false,
+            delete_method: DeleteMethod::MoveToTrash,
+            delete_broken: false,
+            included_directories: Vec::new(),
+            excluded_items: Vec::new(),
+            excluded_directories: Vec::new(),
+            retention: RetentionPolicy::KeepLatest,
+            exclude_globs: default_exclude_globs(),
+            backup_folder_names: default_backup_folder_names(),
+            project_extensions: default_project_extensions(),
+            custom_scan_paths: Vec::new(),
         }
     }
 }
@@ -87,25 +190,91 @@ impl Default for Settings {
 // Structure to represent drive information
 #[derive(Clone)]
 struct DriveInfo {
-    path: PathBuf,
-    name: String,
+    path: PathBuf,        // Mount point
+    name: String,         // Stable selection key (the mount point as a string)
+    label: String,        // Volume label / name reported by the OS
+    fs_type: String,      // Filesystem type (e.g. NTFS, APFS, ext4)
+    total_space: u64,     // Total capacity in bytes
+    available_space: u64, // Free space in bytes
+    is_removable: bool,   // Whether the drive is removable media
     is_selected: bool,
 }
 
-#[derive(Debug, Clone)]
+impl DriveInfo {
+    // A human-friendly one-line description, e.g. "Samples (D:\) — NTFS — 341.0 GB free".
+    fn display_label(&self) -> String {
+        let label = if self.label.is_empty() { self.name.clone() } else { format!("{} ({})", self.label, self.name) };
+        let removable = if self.is_removable { " — removable" } else { "" };
+        format!("{} — {} — {} free{}", label, self.fs_type, humanize_bytes(self.available_space), removable)
+    }
+}
+
+// Result of a cheap structural check on an .flp project file.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum FlpValidity {
+    Valid,     // At least header-chunk length and begins with the "FLhd" magic
+    TooShort,  // Fewer bytes than a header chunk requires
+    BadHeader, // Present but not starting with the "FLhd" magic
+}
+
+impl FlpValidity {
+    fn is_broken(self) -> bool {
+        !matches!(self, FlpValidity::Valid)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct BackupFile {
     path: PathBuf,
     project_name: String,
     timestamp: String,
     file_size: u64,
+    modified_date: u64,              // Last-modified time (seconds since the Unix epoch)
     parsed_time: Option<(u32, u32)>, // (hours, minutes)
+    validity: FlpValidity,           // Structural health of the .flp file
+    #[serde(default)]
+    prehash: u64,                    // Cheap hash of the first few KB, for grouping
+    #[serde(default)]
+    digest: Option<String>,          // Full content digest, filled only for candidates
+}
+
+// Staged progress payload, modelled on a two-phase file scanner: stage 1 counts
+// the files to check, stage 2 does the actual .flp matching against that total.
+#[derive(Debug, Clone)]
+struct ProgressData {
+    message: String,
+    files_checked: usize,
+    files_to_check: usize,
+    current_stage: usize,
+    max_stage: usize,
+}
+
+impl ProgressData {
+    // Stage 1: the fast directory-count pass building a real denominator.
+    fn counting(message: String, files_checked: usize, files_to_check: usize) -> Self {
+        Self { message, files_checked, files_to_check, current_stage: 1, max_stage: 2 }
+    }
+
+    // Stage 2: the actual backup search against the known total.
+    fn matching(message: String, files_checked: usize, files_to_check: usize) -> Self {
+        Self { message, files_checked, files_to_check, current_stage: 2, max_stage: 2 }
+    }
+
+    // Fraction complete in [0, 1], derived from the real total when available.
+    fn fraction(&self) -> f32 {
+        if self.files_to_check == 0 {
+            return 0.0;
+        }
+        (self.files_checked as f32 / self.files_to_check as f32).min(1.0)
+    }
 }
 
 #[derive(Debug)]
 enum ScanMessage {
-    Progress(String, usize, usize), // (message, files_scanned, total_estimated)
+    Progress(ProgressData),
     FoundBackup(String, BackupFile),
     Complete(usize),
+    Cancelled(usize), // Scan was stopped early; carries the count found so far
 }
 
 impl BackupFile {
@@ -126,14 +295,22 @@ impl BackupFile {
         let minutes: u32 = captures.get(3)?.as_str().parse().ok()?;
         let timestamp = format!("{}h{:02}", hours, minutes);
         
-        let file_size = fs::metadata(&path).ok()?.len();
-        
+        let metadata = fs::metadata(&path).ok()?;
+        let file_size = metadata.len();
+        let modified_date = modified_secs(&metadata);
+        let validity = classify_flp(&path);
+        let prehash = content_prehash(&path);
+
         Some(BackupFile {
             path,
             project_name,
             timestamp,
             file_size,
+            modified_date,
             parsed_time: Some((hours, minutes)),
+            validity,
+            prehash,
+            digest: None,
         })
     }
     
@@ -146,6 +323,311 @@ impl BackupFile {
     }
 }
 
+// Persistent scan-result cache so that repeat scans can skip directories whose
+// backup folders have not changed, analogous to Czkawka's on-disk caches.
+#[derive(Default, Serialize, Deserialize)]
+struct ScanCache {
+    entries: HashMap<String, CachedDir>, // Key: backup folder path
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedDir {
+    mtime: u64,               // Backup folder mtime (seconds since the Unix epoch)
+    project_folder: String,   // Parent project path, needed to rebuild the project key
+    backups: Vec<BackupFile>, // The backups found in this folder at cache time
+}
+
+// Last-modified time of a filesystem entry in whole seconds since the Unix epoch.
+fn modified_secs(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// Path of the on-disk scan cache.
+fn get_cache_path() -> PathBuf {
+    let mut path = dirs::cache_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push("flcleaner");
+    fs::create_dir_all(&path).ok();
+    path.push("scan_cache.json");
+    path
+}
+
+fn load_cache() -> ScanCache {
+    let path = get_cache_path();
+    if let Ok(contents) = fs::read_to_string(&path) {
+        if let Ok(cache) = serde_json::from_str(&contents) {
+            return cache;
+        }
+    }
+    ScanCache::default()
+}
+
+fn save_cache(cache: &ScanCache) -> Result<(), Box<dyn std::error::Error>> {
+    let path = get_cache_path();
+    let json = serde_json::to_string_pretty(cache)?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
+// Read the first bytes of an .flp project and classify its integrity. An .flp
+// is a chunked format that must open with an "FLhd" header chunk (ASCII bytes
+// 46 4C 68 64) carrying a 32-bit length and the format/channel/ppq fields,
+// followed by an "FLdt" data chunk. We only inspect the leading magic here.
+fn classify_flp(path: &Path) -> FlpValidity {
+    let mut file = match fs::File::open(path) {
+        Ok(f) => f,
+        Err(_) => return FlpValidity::TooShort,
+    };
+
+    // A valid header chunk is at least 14 bytes (FLhd + len + three u16 fields).
+    let mut header = [0u8; 16];
+    let read = match file.read(&mut header) {
+        Ok(n) => n,
+        Err(_) => return FlpValidity::TooShort,
+    };
+
+    if read < 14 {
+        return FlpValidity::TooShort;
+    }
+
+    if &header[0..4] != b"FLhd" {
+        return FlpValidity::BadHeader;
+    }
+
+    FlpValidity::Valid
+}
+
+// Cheap pre-hash over the first few KB of a file, used to group duplicate
+// candidates before the expensive full-content digest is computed. A 64-bit
+// FNV-1a keeps this allocation-free and fast on the scan hot path.
+fn content_prehash(path: &Path) -> u64 {
+    const PREHASH_BYTES: usize = 8 * 1024;
+    let mut file = match fs::File::open(path) {
+        Ok(f) => f,
+        Err(_) => return 0,
+    };
+    let mut buf = [0u8; PREHASH_BYTES];
+    let read = file.read(&mut buf).unwrap_or(0);
+
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for &byte in &buf[..read] {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+// Full content digest, computed only for files that already share a pre-hash.
+fn content_digest(path: &Path) -> Option<String> {
+    let bytes = fs::read(path).ok()?;
+    Some(blake3::hash(&bytes).to_hex().to_string())
+}
+
+// Given a project's backups sorted newest-first, how many to retain under the
+// configured policy. Always keeps at least one so a project is never emptied.
+fn retained_count(backups: &[BackupFile], retention: RetentionPolicy) -> usize {
+    let keep = match retention {
+        RetentionPolicy::KeepLatest => 1,
+        RetentionPolicy::KeepN(n) => n,
+        RetentionPolicy::KeepNewerThan(days) => {
+            // Keep every backup last modified within `days` of now. Computed
+            // against the wall clock so the window slides as time passes.
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            let cutoff = now.saturating_sub((days as u64).saturating_mul(86_400));
+            backups
+                .iter()
+                .filter(|b| b.modified_date >= cutoff)
+                .count()
+        }
+        RetentionPolicy::MaxTotalMb(cap_mb) => {
+            // Walk newest-first, keeping backups until the running total would
+            // exceed the cap. Assumes `backups` is already sorted latest-first.
+            let cap = cap_mb.saturating_mul(1024 * 1024);
+            let mut running = 0u64;
+            let mut kept = 0usize;
+            for b in backups.iter() {
+                running += b.file_size;
+                if running > cap {
+                    break;
+                }
+                kept += 1;
+            }
+            kept
+        }
+    };
+    keep.clamp(1, backups.len())
+}
+
+// Dispose of a single backup according to the configured method, returning
+// whether it should count toward the tally (a dry run always counts; a real
+// deletion only counts on success).
+fn dispose_backup(method: DeleteMethod, path: &Path) -> bool {
+    match method {
+        DeleteMethod::DryRun => true,
+        DeleteMethod::MoveToTrash => match trash::delete(path) {
+            Ok(_) => true,
+            Err(e) => {
+                eprintln!("Failed to trash {}: {}", path.display(), e);
+                false
+            }
+        },
+        DeleteMethod::PermanentDelete => match fs::remove_file(path) {
+            Ok(_) => true,
+            Err(e) => {
+                eprintln!("Failed to delete {}: {}", path.display(), e);
+                false
+            }
+        },
+    }
+}
+
+// Fill in full content digests for backups that share a pre-hash within the
+// same project, so byte-identical copies can be detected regardless of their
+// timestamp. The expensive full hash is only computed for grouped candidates;
+// unique pre-hashes are left with an empty digest.
+fn compute_duplicate_digests(found: &mut HashMap<String, Vec<BackupFile>>) {
+    for backups in found.values_mut() {
+        // Count how many backups share each (prehash, size) bucket.
+        let mut buckets: HashMap<(u64, u64), usize> = HashMap::new();
+        for b in backups.iter() {
+            *buckets.entry((b.prehash, b.file_size)).or_insert(0) += 1;
+        }
+        for b in backups.iter_mut() {
+            if buckets.get(&(b.prehash, b.file_size)).copied().unwrap_or(0) > 1 {
+                b.digest = content_digest(&b.path);
+            } else {
+                b.digest = None;
+            }
+        }
+    }
+}
+
+// Apply the retention policy to an in-memory set of found backups, disposing of
+// everything past the retained window (and any broken backups first, when
+// requested). Returns the number of files removed, the bytes reclaimed, and the
+// number of disposals that failed so callers (notably the CLI) can surface a
+// non-zero exit. This is the shared core driven by both the GUI "Clean" button
+// and the CLI.
+fn clean_found_backups(
+    found: &mut HashMap<String, Vec<BackupFile>>,
+    method: DeleteMethod,
+    delete_broken: bool,
+    retention: RetentionPolicy,
+) -> (usize, u64, usize) {
+    let mut deleted_count = 0;
+    let mut bytes_saved = 0u64;
+    let mut failed_count = 0usize;
+
+    for backups in found.values_mut() {
+        // First, optionally remove corrupt backups regardless of the
+        // keep-latest rule: a broken newest backup should never be retained.
+        // Broken records tallied here are held aside in dry runs so the
+        // retention pass below cannot tally them a second time; they are
+        // reattached afterwards so the preview still shows them.
+        let mut broken_preview: Vec<BackupFile> = Vec::new();
+        if delete_broken {
+            let mut kept = Vec::with_capacity(backups.len());
+            for backup in backups.drain(..) {
+                if backup.validity.is_broken() {
+                    if dispose_backup(method, &backup.path) {
+                        bytes_saved += backup.file_size;
+                        deleted_count += 1;
+                    } else {
+                        failed_count += 1;
+                    }
+                    if method == DeleteMethod::DryRun {
+                        broken_preview.push(backup);
+                    }
+                } else {
+                    kept.push(backup);
+                }
+            }
+            *backups = kept;
+        }
+
+        // Collapse byte-identical copies regardless of timestamp: among all
+        // backups sharing a content digest, keep the newest and dispose of the
+        // rest. This runs before retention so the age/count policy only ever
+        // sees distinct content.
+        if backups.len() > 1 {
+            let mut seen: HashSet<String> = HashSet::new();
+            let mut ordered: Vec<usize> = (0..backups.len()).collect();
+            // Newest-first so the copy we keep is the most recent.
+            ordered.sort_by(|&a, &b| {
+                backups[b].modified_date.cmp(&backups[a].modified_date)
+            });
+            let mut drop_dupes: Vec<bool> = vec![false; backups.len()];
+            for &i in &ordered {
+                if let Some(digest) = backups[i].digest.clone() {
+                    // A repeated digest means a byte-identical earlier-kept copy.
+                    if !seen.insert(digest) {
+                        if dispose_backup(method, &backups[i].path) {
+                            bytes_saved += backups[i].file_size;
+                            deleted_count += 1;
+                            drop_dupes[i] = true;
+                        } else {
+                            failed_count += 1;
+                        }
+                    }
+                }
+            }
+            if drop_dupes.iter().any(|&d| d) {
+                let mut kept = Vec::with_capacity(backups.len());
+                for (i, backup) in backups.drain(..).enumerate() {
+                    if !drop_dupes[i] {
+                        kept.push(backup);
+                    } else if method == DeleteMethod::DryRun {
+                        // Held aside like the broken records so retention
+                        // cannot tally it a second time.
+                        broken_preview.push(backup);
+                    }
+                }
+                *backups = kept;
+            }
+        }
+
+        // Apply the retention policy to whatever distinct backups remain.
+        if backups.len() > 1 {
+            // Sort newest-first by real modification time so the retained prefix
+            // matches what the policy counts. `get_time_value()` is only a
+            // time-of-day (it wraps every 24h), which would keep/drop the wrong
+            // backups across day boundaries for age- and size-based policies.
+            backups.sort_by(|a, b| b.modified_date.cmp(&a.modified_date));
+
+            // How many of the (now latest-first) backups to retain.
+            let keep = retained_count(backups, retention);
+
+            // Delete everything past the retained window.
+            for backup in backups.iter().skip(keep) {
+                if dispose_backup(method, &backup.path) {
+                    bytes_saved += backup.file_size;
+                    deleted_count += 1;
+                } else {
+                    failed_count += 1;
+                }
+            }
+
+            // A dry run leaves the records untouched so the user can still
+            // review them; a real clean keeps only the retained backups.
+            if method != DeleteMethod::DryRun {
+                backups.truncate(keep);
+            }
+        }
+
+        // Reattach the dry-run broken and duplicate records (already tallied above).
+        backups.append(&mut broken_preview);
+    }
+
+    (deleted_count, bytes_saved, failed_count)
+}
+
 impl FlBackupCleaner {
     fn new() -> Self {
         // Try to load settings from file
@@ -159,22 +641,18 @@ impl FlBackupCleaner {
         // Clear selected drives (we'll rebuild it based on current available drives)
         settings.selected_drives.clear();
         
-        // Convert drives to DriveInfo and set selection state based on saved settings
+        // Set selection state on the enumerated drives based on saved settings
         let available_drives = available_drives.into_iter()
-            .map(|path| {
-                let name = path.display().to_string();
-                let is_selected = saved_selected_drives.contains(&name);
-                
+            .map(|mut drive| {
+                let is_selected = saved_selected_drives.contains(&drive.name);
+
                 // Add to selected drives if it was previously selected or if there were no saved selections
                 if is_selected || saved_selected_drives.is_empty() {
-                    settings.selected_drives.insert(name.clone());
-                }
-                
-                DriveInfo {
-                    path,
-                    name,
-                    is_selected: is_selected || saved_selected_drives.is_empty(),
+                    settings.selected_drives.insert(drive.name.clone());
                 }
+
+                drive.is_selected = is_selected || saved_selected_drives.is_empty();
+                drive
             })
             .collect();
         
@@ -206,52 +684,104 @@ impl FlBackupCleaner {
         self.scan_start_time = Some(Instant::now());
         self.error_messages.clear();
         self.detailed_progress.clear();
-        
+        self.scan_stage = (0, 0);
+
         let (tx, rx) = mpsc::channel();
         self.scan_receiver = Some(rx);
-        
-        // Get selected drives
-        let selected_drives: Vec<PathBuf> = self.available_drives.iter()
-            .filter(|drive| self.settings.selected_drives.contains(&drive.name))
-            .map(|drive| drive.path.clone())
-            .collect();
-            
+
+        // Cancellation: the UI holds the sender, every worker holds a cloned receiver.
+        let (stop_tx, stop_rx) = crossbeam_channel::unbounded::<()>();
+        self.stop_sender = Some(stop_tx);
+        // Cooperative cancellation token, reset to false for each fresh scan so a
+        // prior cancellation never aborts the next run. Shared with every worker.
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.stop_flag = Some(Arc::clone(&cancelled));
+
+        // Determine the scan roots: explicit included directories take precedence
+        // over whole drives, letting users confine a scan to (say) their Projects
+        // folder.
+        let mut selected_drives: Vec<PathBuf> = if !self.settings.included_directories.is_empty() {
+            self.settings.included_directories.clone()
+        } else {
+            self.available_drives.iter()
+                .filter(|drive| self.settings.selected_drives.contains(&drive.name))
+                .map(|drive| drive.path.clone())
+                .collect()
+        };
+        // Custom folders picked via the directory dialog are always scanned,
+        // on top of whichever drives or included roots are active.
+        for path in &self.settings.custom_scan_paths {
+            if !selected_drives.contains(path) {
+                selected_drives.push(path.clone());
+            }
+        }
+
         // Get scan settings
         let max_threads = self.settings.max_scan_threads;
         let max_depth = self.settings.scan_depth;
         let auto_clean = self.settings.auto_clean;
+        // Excluded directories prune whole subtrees: their paths are folded in
+        // alongside the substring patterns, since a directory path is a prefix
+        // (and thus substring) of every path beneath it.
+        let mut excluded = self.settings.excluded_items.clone();
+        excluded.extend(
+            self.settings
+                .excluded_directories
+                .iter()
+                .map(|p| p.display().to_string()),
+        );
+        let excluded_items = Arc::new(excluded);
+        // Compile glob/name/extension rules once for the whole scan.
+        let match_rules = Arc::new(MatchRules::from_settings(&self.settings));
         
         // Spawn background thread for scanning
         thread::spawn(move || {
-            let _ = tx.send(ScanMessage::Progress("Getting drive list...".to_string(), 0, 0));
-            
             let drives = selected_drives;
             let _total_drives = drives.len();
-            
-            // We'll set a fixed estimate initially and adjust it as scanning proceeds
-            // This avoids the progress bar showing more files than total
-            let base_estimate_per_drive = 100000; // Lower initial estimate to avoid jumps
-            let total_estimated_files = drives.len() * base_estimate_per_drive;
-            
+
+            // Stage 1: a fast directory-count walk to compute the real number of
+            // files to check, so the progress bar has an honest denominator.
+            let _ = tx.send(ScanMessage::Progress(ProgressData::counting(
+                "Stage 1/2: Counting files...".to_string(), 0, 0,
+            )));
+            let mut files_to_check = 0usize;
+            for drive in drives.iter() {
+                if cancelled.load(Ordering::Relaxed) {
+                    break;
+                }
+                let drive_total = count_files(drive, max_depth, &cancelled, &stop_rx, &excluded_items, &match_rules);
+                files_to_check += drive_total;
+                let _ = tx.send(ScanMessage::Progress(ProgressData::counting(
+                    format!("Stage 1/2: Counting {} ({} files so far)", drive.display(), files_to_check),
+                    0,
+                    files_to_check,
+                )));
+            }
+            // Avoid a zero denominator if counting was interrupted or empty.
+            let files_to_check = files_to_check.max(1);
+
             let files_scanned = Arc::new(Mutex::new(0usize));
-            let total_estimate = Arc::new(Mutex::new(total_estimated_files));
+            let total_estimate = Arc::new(Mutex::new(files_to_check));
             let mut total_found = 0;
             let mut scan_threads = Vec::new();
-            
+
             // Track which drives have been completed
             let completed_drives = Arc::new(Mutex::new(HashSet::new()));
-            
-            // Send initial estimate
-            let _ = tx.send(ScanMessage::Progress(
-                format!("Starting scan across {} drives", drives.len()),
+
+            // Stage 2 begins.
+            let _ = tx.send(ScanMessage::Progress(ProgressData::matching(
+                format!("Stage 2/2: Scanning across {} drives", drives.len()),
                 0,
-                *total_estimate.lock().unwrap()
-            ));
-            
+                files_to_check,
+            )));
+
             // Create a shared set of already processed directories to avoid duplicates
             let processed_dirs = Arc::new(Mutex::new(HashSet::new()));
-            
-            for (_, drive) in drives.iter().enumerate() {
+
+            // Load the persistent cache so unchanged backup folders can be reused.
+            let cache = Arc::new(Mutex::new(load_cache()));
+
+            for drive in drives.iter() {
                 let drive_name = drive.display().to_string();
                 let tx_clone = tx.clone();
                 let drive_clone = drive.clone();
@@ -259,13 +789,18 @@ impl FlBackupCleaner {
                 let total_estimate_clone = Arc::clone(&total_estimate);
                 let processed_dirs_clone = Arc::clone(&processed_dirs);
                 let completed_drives_clone = Arc::clone(&completed_drives);
-                
-                let _ = tx.send(ScanMessage::Progress(
+                let cancelled_drive = Arc::clone(&cancelled);
+                let stop_rx_drive = stop_rx.clone();
+                let cache_drive = Arc::clone(&cache);
+                let excluded_drive = Arc::clone(&excluded_items);
+                let rules_drive = Arc::clone(&match_rules);
+
+                let _ = tx.send(ScanMessage::Progress(ProgressData::matching(
                     format!("Scanning {}", drive_name),
                     *files_scanned.lock().unwrap(),
-                    *total_estimate.lock().unwrap()
-                ));
-                
+                    *total_estimate.lock().unwrap(),
+                )));
+
                 // Create multiple scan threads per drive to improve performance
                 // Use the configured number of threads per drive
                 let handle = thread::spawn(move || {
@@ -276,11 +811,11 @@ impl FlBackupCleaner {
                     
                     // Report if no top directories were found
                     if top_dirs.is_empty() {
-                        let _ = tx_clone.send(ScanMessage::Progress(
+                        let _ = tx_clone.send(ScanMessage::Progress(ProgressData::matching(
                             format!("No scannable directories found on {}", drive_name),
                             *files_scanned_clone.lock().unwrap(),
-                            *total_estimate_clone.lock().unwrap()
-                        ));
+                            *total_estimate_clone.lock().unwrap(),
+                        )));
                         
                         // Mark this drive as completed
                         completed_drives_clone.lock().unwrap().insert(drive_name.clone());
@@ -301,17 +836,26 @@ impl FlBackupCleaner {
                         let files_scanned_global = Arc::clone(&files_scanned_clone);
                         let total_estimate_thread = Arc::clone(&total_estimate_clone);
                         let processed_dirs_thread = Arc::clone(&processed_dirs_clone);
-                        
+                        let cancelled_thread = Arc::clone(&cancelled_drive);
+                        let stop_rx_thread = stop_rx_drive.clone();
+                        let cache_thread = Arc::clone(&cache_drive);
+                        let excluded_thread = Arc::clone(&excluded_drive);
+                        let rules_thread = Arc::clone(&rules_drive);
+
                         let thread_handle = thread::spawn(move || {
                             let mut dirs_found = 0;
-                            
+
                             for dir in chunk_dirs {
+                                // Stop promptly if a cancellation has been requested.
+                                if cancelled_thread.load(Ordering::Relaxed) {
+                                    break;
+                                }
                                 // Update that we're scanning this directory
-                                let _ = tx_thread.send(ScanMessage::Progress(
+                                let _ = tx_thread.send(ScanMessage::Progress(ProgressData::matching(
                                     format!("Scanning {}", dir.display()),
                                     *files_scanned_global.lock().unwrap(),
-                                    *total_estimate_thread.lock().unwrap()
-                                ));
+                                    *total_estimate_thread.lock().unwrap(),
+                                )));
                                 
                                 scan_directory(
                                     &dir, 
@@ -321,15 +865,20 @@ impl FlBackupCleaner {
                                     files_scanned_global.clone(),
                                     total_estimate_thread.clone(),
                                     processed_dirs_thread.clone(),
-                                    max_depth  // Use configured max depth
+                                    max_depth,  // Use configured max depth
+                                    &cancelled_thread,
+                                    &stop_rx_thread,
+                                    &cache_thread,
+                                    &excluded_thread,
+                                    &rules_thread,
                                 ).unwrap_or_else(|e| {
                                     eprintln!("Error scanning {}: {}", dir.display(), e);
                                     // Send error to UI
-                                    let _ = tx_thread.send(ScanMessage::Progress(
+                                    let _ = tx_thread.send(ScanMessage::Progress(ProgressData::matching(
                                         format!("Error scanning {}: {}", dir.display(), e),
                                         *files_scanned_global.lock().unwrap(),
-                                        *total_estimate_thread.lock().unwrap()
-                                    ));
+                                        *total_estimate_thread.lock().unwrap(),
+                                    )));
                                 });
                             }
                             
@@ -346,21 +895,21 @@ impl FlBackupCleaner {
                             Err(e) => {
                                 eprintln!("Thread join error: {:?}", e);
                                 // Send error to UI
-                                let _ = tx_clone.send(ScanMessage::Progress(
+                                let _ = tx_clone.send(ScanMessage::Progress(ProgressData::matching(
                                     format!("Thread error on {}", drive_name),
                                     *files_scanned_clone.lock().unwrap(),
-                                    *total_estimate_clone.lock().unwrap()
-                                ));
+                                    *total_estimate_clone.lock().unwrap(),
+                                )));
                             }
                         }
                     }
                     
                     // Report completion for this drive
-                    let _ = tx_clone.send(ScanMessage::Progress(
+                    let _ = tx_clone.send(ScanMessage::Progress(ProgressData::matching(
                         format!("Completed scan of {}", drive_name),
                         *files_scanned_clone.lock().unwrap(),
-                        *total_estimate_clone.lock().unwrap()
-                    ));
+                        *total_estimate_clone.lock().unwrap(),
+                    )));
                     
                     // Mark this drive as completed
                     completed_drives_clone.lock().unwrap().insert(drive_name);
@@ -398,49 +947,58 @@ impl FlBackupCleaner {
                         "Warning: Some drives were not fully scanned: {}",
                         missing_drives.join(", ")
                     );
-                    let _ = tx.send(ScanMessage::Progress(
+                    let _ = tx.send(ScanMessage::Progress(ProgressData::matching(
                         message,
                         *files_scanned.lock().unwrap(),
-                        *total_estimate.lock().unwrap()
-                    ));
+                        *total_estimate.lock().unwrap(),
+                    )));
                 }
             }
             
             // Final update before completion
             let final_scanned = *files_scanned.lock().unwrap();
             let final_estimate = *total_estimate.lock().unwrap();
-            let _ = tx.send(ScanMessage::Progress(
+            let _ = tx.send(ScanMessage::Progress(ProgressData::matching(
                 format!("Scan completed. Processed {} files", final_scanned),
                 final_scanned,
-                final_estimate
-            ));
-            
+                final_estimate,
+            )));
+
             // Ensure we've scanned a reasonable number of files before completing
             // This helps prevent premature completion
             if final_scanned < 1000 {
                 // Very few files scanned, might be an issue
-                let _ = tx.send(ScanMessage::Progress(
+                let _ = tx.send(ScanMessage::Progress(ProgressData::matching(
                     format!("Warning: Only {} files were scanned. Some drives may have been skipped.", final_scanned),
                     final_scanned,
-                    final_estimate
-                ));
+                    final_estimate,
+                )));
                 
                 // Add a small delay to ensure the message is seen
                 thread::sleep(Duration::from_millis(500));
             }
             
-            // Send completion message
+            // Persist the updated cache for faster repeat scans.
+            if let Err(e) = save_cache(&cache.lock().unwrap()) {
+                eprintln!("Failed to save scan cache: {}", e);
+            }
+
+            // Send completion message (or a cancellation notice if stopped early)
+            if cancelled.load(Ordering::Relaxed) {
+                let _ = tx.send(ScanMessage::Cancelled(total_found));
+                return;
+            }
             let _ = tx.send(ScanMessage::Complete(total_found));
-            
+
             // Auto-clean if enabled
             if auto_clean && total_found > 0 {
                 // Signal that auto-clean should happen
                 // We'll handle this in the update_from_scan_messages method
-                let _ = tx.send(ScanMessage::Progress(
+                let _ = tx.send(ScanMessage::Progress(ProgressData::matching(
                     "AUTO_CLEAN".to_string(),
                     final_scanned,
-                    final_estimate
-                ));
+                    final_estimate,
+                )));
             }
         });
     }
@@ -453,13 +1011,13 @@ impl FlBackupCleaner {
         if let Some(receiver) = &self.scan_receiver {
             // Collect all available messages
             while let Ok(message) = receiver.try_recv() {
-                if matches!(message, ScanMessage::Complete(_)) {
+                if matches!(message, ScanMessage::Complete(_) | ScanMessage::Cancelled(_)) {
                     should_clear_receiver = true;
                 }
                 
                 // Check for auto-clean signal
-                if let ScanMessage::Progress(msg, _, _) = &message {
-                    if msg == "AUTO_CLEAN" {
+                if let ScanMessage::Progress(data) = &message {
+                    if data.message == "AUTO_CLEAN" {
                         should_auto_clean = true;
                         continue; // Skip adding this message to the list
                     }
@@ -472,12 +1030,14 @@ impl FlBackupCleaner {
         // Process messages after borrowing is done
         for message in messages {
             match message {
-                ScanMessage::Progress(msg, files_scanned, total_files) => {
+                ScanMessage::Progress(data) => {
+                    let msg = data.message.clone();
                     self.scan_progress = msg.clone();
-                    self.files_scanned = files_scanned;
-                    
+                    self.files_scanned = data.files_checked;
+                    self.scan_stage = (data.current_stage, data.max_stage);
+
                     // Add to detailed progress log if it contains important info
-                    if msg.contains("Error") || msg.contains("Found FL Studio") || 
+                    if msg.contains("Error") || msg.contains("Found FL Studio") ||
                        msg.contains("Completed scan of") || msg.contains("Scan completed") {
                         // Limit the number of progress messages to avoid memory issues
                         if self.detailed_progress.len() > 100 {
@@ -485,7 +1045,7 @@ impl FlBackupCleaner {
                         }
                         self.detailed_progress.push(msg.clone());
                     }
-                    
+
                     // Record error messages separately
                     if msg.contains("Error") {
                         // Limit the number of error messages to avoid memory issues
@@ -494,17 +1054,12 @@ impl FlBackupCleaner {
                         }
                         self.error_messages.push(msg);
                     }
-                    
-                    if total_files > 0 {
-                        self.total_files_estimated = total_files;
-                        self.progress_percentage = (files_scanned as f32 / total_files as f32) * 100.0;
-                        
-                        // Ensure progress doesn't exceed 100%
-                        if self.progress_percentage > 100.0 {
-                            self.progress_percentage = 100.0;
-                        }
+
+                    if data.files_to_check > 0 {
+                        self.total_files_estimated = data.files_to_check;
+                        self.progress_percentage = data.fraction() * 100.0;
                     }
-                    
+
                     ctx.request_repaint();
                 }
                 ScanMessage::FoundBackup(project_key, backup_file) => {
@@ -526,14 +1081,33 @@ impl FlBackupCleaner {
                     self.scan_complete = true;
                     self.progress_percentage = 100.0;
                     self.files_scanned = self.total_files_estimated;
+                    compute_duplicate_digests(&mut self.found_backups);
+                    ctx.request_repaint();
+                }
+                ScanMessage::Cancelled(total_found) => {
+                    self.total_files_found = total_found;
+                    self.scan_status = format!(
+                        "Scan cancelled — showing {} backups found in {} projects so far",
+                        self.found_backups.values().map(|v| v.len()).sum::<usize>(),
+                        self.found_backups.len()
+                    );
+                    self.scan_progress = String::new();
+                    self.is_scanning = false;
+                    // Keep any partial results on screen rather than discarding them.
+                    self.scan_complete = true;
+                    self.progress_percentage = 0.0;
+                    should_auto_clean = false;
+                    compute_duplicate_digests(&mut self.found_backups);
                     ctx.request_repaint();
                 }
             }
         }
-        
+
         // Clear receiver after processing all messages
         if should_clear_receiver {
             self.scan_receiver = None;
+            self.stop_sender = None;
+            self.stop_flag = None;
             
             // Auto-clean if needed
             if should_auto_clean {
@@ -542,41 +1116,51 @@ impl FlBackupCleaner {
         }
     }
     
-    fn clean_backups(&mut self) {
-        self.deletion_status = "Cleaning backup files...".to_string();
-        self.total_size_saved = 0;
-        let mut deleted_count = 0;
-        
-        for (_project_key, backups) in &mut self.found_backups {
-            if backups.len() <= 1 {
-                continue; // Keep single backup files
-            }
-            
-            // Sort by time (latest first)
-            backups.sort_by(|a, b| b.get_time_value().cmp(&a.get_time_value()));
-            
-            // Keep the first (latest) backup, delete the rest
-            for backup in backups.iter().skip(1) {
-                match fs::remove_file(&backup.path) {
-                    Ok(_) => {
-                        self.total_size_saved += backup.file_size;
-                        deleted_count += 1;
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to delete {}: {}", backup.path.display(), e);
-                    }
-                }
+    fn cancel_scan(&mut self) {
+        // Set the shared stop flag directly so every worker observes it, and also
+        // send on the stop channel, which workers poll alongside the flag.
+        if let Some(flag) = &self.stop_flag {
+            flag.store(true, Ordering::Relaxed);
+        }
+        if let Some(sender) = &self.stop_sender {
+            for _ in 0..self.settings.max_scan_threads.max(1) {
+                let _ = sender.send(());
             }
-            
-            // Keep only the latest backup in our records
-            backups.truncate(1);
         }
-        
-        self.deletion_status = format!(
-            "Cleanup complete! Deleted {} files, saved {:.2} MB", 
-            deleted_count, 
-            self.total_size_saved as f64 / (1024.0 * 1024.0)
+        self.scan_progress = "Stopping scan...".to_string();
+    }
+
+    fn clean_backups(&mut self) {
+        let method = self.settings.delete_method;
+        self.deletion_status = match method {
+            DeleteMethod::DryRun => "Previewing backup cleanup (dry run)...".to_string(),
+            _ => "Cleaning backup files...".to_string(),
+        };
+
+        // Share the disposal/retention logic with the headless CLI path.
+        let (deleted_count, bytes, _failed) = clean_found_backups(
+            &mut self.found_backups,
+            method,
+            self.settings.delete_broken,
+            self.settings.retention,
         );
+        self.total_size_saved = bytes;
+
+        let saved_mb = self.total_size_saved as f64 / (1024.0 * 1024.0);
+        self.deletion_status = match method {
+            DeleteMethod::DryRun => format!(
+                "Dry run: {} files ({:.2} MB) would be removed. No files were touched.",
+                deleted_count, saved_mb
+            ),
+            DeleteMethod::MoveToTrash => format!(
+                "Cleanup complete! Moved {} files to the recycle bin, freed {:.2} MB",
+                deleted_count, saved_mb
+            ),
+            DeleteMethod::PermanentDelete => format!(
+                "Cleanup complete! Deleted {} files, saved {:.2} MB",
+                deleted_count, saved_mb
+            ),
+        };
     }
     
     fn refresh_drives(&mut self) {
@@ -589,22 +1173,17 @@ impl FlBackupCleaner {
         
         // Update available drives list
         self.available_drives = current_drives.into_iter()
-            .map(|path| {
-                let name = path.display().to_string();
-                
+            .map(|mut drive| {
                 // Check if this drive was previously selected
-                let is_selected = saved_selected_drives.contains(&name);
-                
+                let is_selected = saved_selected_drives.contains(&drive.name);
+
                 // Add to selected drives if it was previously selected
                 if is_selected {
-                    self.settings.selected_drives.insert(name.clone());
-                }
-                
-                DriveInfo {
-                    path,
-                    name,
-                    is_selected,
+                    self.settings.selected_drives.insert(drive.name.clone());
                 }
+
+                drive.is_selected = is_selected;
+                drive
             })
             .collect();
             
@@ -625,7 +1204,12 @@ impl FlBackupCleaner {
         let mut should_close = false;
         let mut should_apply = false;
         let mut should_refresh = false;
-        
+        let mut include_input = self.include_input.clone();
+        let mut exclude_input = self.exclude_input.clone();
+        let mut glob_input = self.glob_input.clone();
+        let mut backup_name_input = self.backup_name_input.clone();
+        let mut ext_input = self.ext_input.clone();
+
         // Create a modal dialog for settings
         egui::Window::new("Settings")
             .collapsible(false)
@@ -711,7 +1295,7 @@ impl FlBackupCleaner {
                             // Drive checkboxes
                             for drive in &mut drives {
                                 let mut is_selected = settings.selected_drives.contains(&drive.name);
-                                if ui.checkbox(&mut is_selected, &drive.name).changed() {
+                                if ui.checkbox(&mut is_selected, drive.display_label()).changed() {
                                     if is_selected {
                                         settings.selected_drives.insert(drive.name.clone());
                                     } else {
@@ -723,9 +1307,219 @@ impl FlBackupCleaner {
                             }
                         });
                     });
-                
+
+                ui.add_space(8.0);
+
+                // Custom scan folders: arbitrary directories picked through the
+                // native dialog, scanned in addition to the selected drives.
+                ui.label("Add individual folders to scan alongside the drives above:");
+                if ui.button(egui::RichText::new("ðŸ“ Add Folder...").size(14.0)).clicked() {
+                    if let Some(folder) = rfd::FileDialog::new().pick_folder() {
+                        if !settings.custom_scan_paths.contains(&folder) {
+                            settings.custom_scan_paths.push(folder);
+                            should_apply = true;
+                        }
+                    }
+                }
+                if !settings.custom_scan_paths.is_empty() {
+                    egui::Frame::group(ui.style())
+                        .fill(ui.visuals().widgets.noninteractive.bg_fill)
+                        .show(ui, |ui| {
+                            let mut remove_custom = None;
+                            for (i, path) in settings.custom_scan_paths.iter().enumerate() {
+                                ui.horizontal(|ui| {
+                                    ui.label(path.display().to_string());
+                                    if ui.small_button("âœ–").clicked() {
+                                        remove_custom = Some(i);
+                                    }
+                                });
+                            }
+                            if let Some(i) = remove_custom {
+                                settings.custom_scan_paths.remove(i);
+                                should_apply = true;
+                            }
+                        });
+                }
+
+                ui.add_space(15.0);
+
+                // Scan scope section: included roots and excluded patterns.
+                ui.heading(egui::RichText::new("ðŸ—‚ Scan Scope").size(16.0).strong());
+                ui.add_space(5.0);
+                ui.label("Limit scanning to specific folders and skip unwanted paths.");
+
+                egui::Frame::group(ui.style())
+                    .fill(ui.visuals().widgets.noninteractive.bg_fill)
+                    .show(ui, |ui| {
+                        // Included directories: when non-empty, these roots are scanned
+                        // instead of whole drives.
+                        ui.label(egui::RichText::new("Included directories:").strong());
+                        ui.horizontal(|ui| {
+                            ui.text_edit_singleline(&mut include_input);
+                            if ui.button("Add").clicked() && !include_input.trim().is_empty() {
+                                settings.included_directories.push(PathBuf::from(include_input.trim()));
+                                include_input.clear();
+                                should_apply = true;
+                            }
+                        });
+                        let mut remove_include = None;
+                        for (i, dir) in settings.included_directories.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.label(dir.display().to_string());
+                                if ui.small_button("âœ–").clicked() {
+                                    remove_include = Some(i);
+                                }
+                            });
+                        }
+                        if let Some(i) = remove_include {
+                            settings.included_directories.remove(i);
+                            should_apply = true;
+                        }
+
+                        ui.separator();
+
+                        // Excluded items: substring or wildcard patterns pruned
+                        // during the walk (e.g. `*\Temp\*`).
+                        ui.label(egui::RichText::new("Excluded items (substring or wildcard, e.g. *\\Temp\\*):").strong());
+                        ui.horizontal(|ui| {
+                            ui.text_edit_singleline(&mut exclude_input);
+                            if ui.button("Add").clicked() && !exclude_input.trim().is_empty() {
+                                settings.excluded_items.push(exclude_input.trim().to_string());
+                                exclude_input.clear();
+                                should_apply = true;
+                            }
+                        });
+                        let mut remove_exclude = None;
+                        for (i, item) in settings.excluded_items.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.label(item);
+                                if ui.small_button("âœ–").clicked() {
+                                    remove_exclude = Some(i);
+                                }
+                            });
+                        }
+                        if let Some(i) = remove_exclude {
+                            settings.excluded_items.remove(i);
+                            should_apply = true;
+                        }
+
+                        ui.separator();
+
+                        // Excluded directories: whole subtrees skipped early in the
+                        // walk, e.g. a network or system drive.
+                        ui.label(egui::RichText::new("Excluded directories:").strong());
+                        if ui.button("Add folder to exclude...").clicked() {
+                            if let Some(folder) = rfd::FileDialog::new().pick_folder() {
+                                if !settings.excluded_directories.contains(&folder) {
+                                    settings.excluded_directories.push(folder);
+                                    should_apply = true;
+                                }
+                            }
+                        }
+                        let mut remove_excl_dir = None;
+                        for (i, dir) in settings.excluded_directories.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.label(dir.display().to_string());
+                                if ui.small_button("âœ–").clicked() {
+                                    remove_excl_dir = Some(i);
+                                }
+                            });
+                        }
+                        if let Some(i) = remove_excl_dir {
+                            settings.excluded_directories.remove(i);
+                            should_apply = true;
+                        }
+                    });
+
+                ui.add_space(15.0);
+
+                // Exclusions & matching section: glob excludes, backup-folder names
+                // and recognised project extensions.
+                ui.heading(egui::RichText::new("ðŸ”Ž Exclusions & Matching").size(16.0).strong());
+                ui.add_space(5.0);
+
+                egui::Frame::group(ui.style())
+                    .fill(ui.visuals().widgets.noninteractive.bg_fill)
+                    .show(ui, |ui| {
+                        // Exclude globs.
+                        ui.label(egui::RichText::new("Exclude globs:").strong());
+                        ui.horizontal(|ui| {
+                            ui.text_edit_singleline(&mut glob_input);
+                            if ui.button("Add").clicked() && !glob_input.trim().is_empty() {
+                                settings.exclude_globs.push(glob_input.trim().to_string());
+                                glob_input.clear();
+                                should_apply = true;
+                            }
+                        });
+                        let mut remove_glob = None;
+                        for (i, g) in settings.exclude_globs.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.label(g);
+                                if ui.small_button("âœ–").clicked() {
+                                    remove_glob = Some(i);
+                                }
+                            });
+                        }
+                        if let Some(i) = remove_glob {
+                            settings.exclude_globs.remove(i);
+                            should_apply = true;
+                        }
+
+                        ui.separator();
+
+                        // Backup folder names.
+                        ui.label(egui::RichText::new("Backup folder names:").strong());
+                        ui.horizontal(|ui| {
+                            ui.text_edit_singleline(&mut backup_name_input);
+                            if ui.button("Add").clicked() && !backup_name_input.trim().is_empty() {
+                                settings.backup_folder_names.push(backup_name_input.trim().to_string());
+                                backup_name_input.clear();
+                                should_apply = true;
+                            }
+                        });
+                        let mut remove_name = None;
+                        for (i, n) in settings.backup_folder_names.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.label(n);
+                                if ui.small_button("âœ–").clicked() {
+                                    remove_name = Some(i);
+                                }
+                            });
+                        }
+                        if let Some(i) = remove_name {
+                            settings.backup_folder_names.remove(i);
+                            should_apply = true;
+                        }
+
+                        ui.separator();
+
+                        // Project extensions.
+                        ui.label(egui::RichText::new("Project extensions:").strong());
+                        ui.horizontal(|ui| {
+                            ui.text_edit_singleline(&mut ext_input);
+                            if ui.button("Add").clicked() && !ext_input.trim().is_empty() {
+                                settings.project_extensions.push(ext_input.trim().trim_start_matches('.').to_string());
+                                ext_input.clear();
+                                should_apply = true;
+                            }
+                        });
+                        let mut remove_ext = None;
+                        for (i, e) in settings.project_extensions.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.label(e);
+                                if ui.small_button("âœ–").clicked() {
+                                    remove_ext = Some(i);
+                                }
+                            });
+                        }
+                        if let Some(i) = remove_ext {
+                            settings.project_extensions.remove(i);
+                            should_apply = true;
+                        }
+                    });
+
                 ui.add_space(15.0);
-                
+
                 // Performance settings section
                 ui.heading(egui::RichText::new("âš¡ Performance").size(16.0).strong());
                 ui.add_space(5.0);
@@ -784,6 +1578,93 @@ impl FlBackupCleaner {
                                  after the scan completes, keeping only the latest backup for each project."
                             );
                         });
+
+                        ui.add_space(5.0);
+
+                        // How old backups are disposed of when cleaning.
+                        ui.horizontal(|ui| {
+                            ui.label("Delete method:");
+                            if ui.radio_value(&mut settings.delete_method, DeleteMethod::DryRun, "Dry run").clicked() {
+                                should_apply = true;
+                            }
+                            if ui.radio_value(&mut settings.delete_method, DeleteMethod::MoveToTrash, "Recycle bin").clicked() {
+                                should_apply = true;
+                            }
+                            if ui.radio_value(&mut settings.delete_method, DeleteMethod::PermanentDelete, "Permanent").clicked() {
+                                should_apply = true;
+                            }
+                            ui.label("â„¹ï¸").on_hover_text(
+                                "Dry run previews what would be removed without deleting anything.\n\
+                                 Recycle bin moves backups to the OS trash so they can be recovered.\n\
+                                 Permanent removes backups immediately and cannot be undone."
+                            );
+                        });
+
+                        ui.add_space(5.0);
+
+                        // Corrupt backups get removed even if they are the newest.
+                        ui.horizontal(|ui| {
+                            if ui.checkbox(&mut settings.delete_broken, "Also remove broken/corrupt backups").changed() {
+                                should_apply = true;
+                            }
+                            ui.label("â„¹ï¸").on_hover_text(
+                                "When enabled, .flp backups that fail the integrity check are removed\n\
+                                 regardless of the keep-latest rule, so a corrupt newest backup is not retained."
+                            );
+                        });
+
+                        ui.add_space(5.0);
+
+                        // Retention policy: how many backups to keep per project.
+                        ui.label(egui::RichText::new("Retention policy:").strong());
+                        let mut policy_kind = match settings.retention {
+                            RetentionPolicy::KeepLatest => 0,
+                            RetentionPolicy::KeepN(_) => 1,
+                            RetentionPolicy::KeepNewerThan(_) => 2,
+                            RetentionPolicy::MaxTotalMb(_) => 3,
+                        };
+                        ui.horizontal(|ui| {
+                            if ui.radio_value(&mut policy_kind, 0, "Keep latest").clicked() {
+                                settings.retention = RetentionPolicy::KeepLatest;
+                                should_apply = true;
+                            }
+                            if ui.radio_value(&mut policy_kind, 1, "Keep N recent").clicked() {
+                                let n = match settings.retention { RetentionPolicy::KeepN(n) => n, _ => 3 };
+                                settings.retention = RetentionPolicy::KeepN(n);
+                                should_apply = true;
+                            }
+                            if ui.radio_value(&mut policy_kind, 2, "Newer than").clicked() {
+                                let c = match settings.retention { RetentionPolicy::KeepNewerThan(c) => c, _ => 30 };
+                                settings.retention = RetentionPolicy::KeepNewerThan(c);
+                                should_apply = true;
+                            }
+                            if ui.radio_value(&mut policy_kind, 3, "Size cap").clicked() {
+                                let m = match settings.retention { RetentionPolicy::MaxTotalMb(m) => m, _ => 100 };
+                                settings.retention = RetentionPolicy::MaxTotalMb(m);
+                                should_apply = true;
+                            }
+                        });
+                        match settings.retention {
+                            RetentionPolicy::KeepN(mut n) => {
+                                if ui.add(egui::Slider::new(&mut n, 1..=20).text("backups")).changed() {
+                                    settings.retention = RetentionPolicy::KeepN(n);
+                                    should_apply = true;
+                                }
+                            }
+                            RetentionPolicy::KeepNewerThan(mut c) => {
+                                if ui.add(egui::Slider::new(&mut c, 1..=365).text("age (days)")).changed() {
+                                    settings.retention = RetentionPolicy::KeepNewerThan(c);
+                                    should_apply = true;
+                                }
+                            }
+                            RetentionPolicy::MaxTotalMb(mut m) => {
+                                if ui.add(egui::Slider::new(&mut m, 10..=2000).text("MB per project")).changed() {
+                                    settings.retention = RetentionPolicy::MaxTotalMb(m);
+                                    should_apply = true;
+                                }
+                            }
+                            RetentionPolicy::KeepLatest => {}
+                        }
                     });
                 
                 ui.add_space(20.0);
@@ -810,6 +1691,13 @@ impl FlBackupCleaner {
                 });
             });
             
+        // Preserve the in-progress text entries across frames.
+        self.include_input = include_input;
+        self.exclude_input = exclude_input;
+        self.glob_input = glob_input;
+        self.backup_name_input = backup_name_input;
+        self.ext_input = ext_input;
+
         // Apply settings immediately when they change
         if should_apply {
             self.settings = settings.clone();
@@ -872,32 +1760,42 @@ impl FlBackupCleaner {
     }
 }
 
-fn get_all_drives() -> Vec<PathBuf> {
-    let mut drives = Vec::new();
-    
-    #[cfg(target_os = "windows")]
-    {
-        // On Windows, check drives A: through Z:
-        for letter in b'A'..=b'Z' {
-            let drive = format!("{}:\\", letter as char);
-            let path = PathBuf::from(&drive);
-            if path.exists() {
-                drives.push(path);
-            }
-        }
+// Humanize a byte count using 1024-based units with one decimal (e.g. "341.0 GB").
+fn humanize_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["B", "KB", "MB", "GB", "TB", "PB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
     }
-    
-    #[cfg(not(target_os = "windows"))]
-    {
-        // On Unix-like systems, start from root and common mount points
-        drives.push(PathBuf::from("/"));
-        drives.push(PathBuf::from("/home"));
-        drives.push(PathBuf::from("/Users")); // macOS
-        drives.push(PathBuf::from("/mnt"));   // Linux mount points
-        drives.push(PathBuf::from("/media")); // Linux removable media
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
     }
-    
-    drives
+}
+
+// Enumerate the real mounted filesystems, with labels, type and free space, so
+// the user can tell an external sample drive from a system partition.
+fn get_all_drives() -> Vec<DriveInfo> {
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks
+        .iter()
+        .map(|disk| {
+            let path = disk.mount_point().to_path_buf();
+            DriveInfo {
+                name: path.display().to_string(),
+                label: disk.name().to_string_lossy().to_string(),
+                fs_type: disk.file_system().to_string_lossy().to_string(),
+                total_space: disk.total_space(),
+                available_space: disk.available_space(),
+                is_removable: disk.is_removable(),
+                path,
+                is_selected: false,
+            }
+        })
+        .collect()
 }
 
 // Get the top-level directories in a drive to distribute work among threads
@@ -936,6 +1834,134 @@ fn get_top_level_directories(drive: &Path) -> Vec<PathBuf> {
     dirs
 }
 
+// Compiled matching rules, built once per scan from the user's settings and
+// shared (read-only) across every worker thread.
+struct MatchRules {
+    exclude_set: globset::GlobSet,      // Compiled exclude globs
+    backup_folder_names: Vec<String>,   // Folder names treated as backup folders
+    project_extensions: Vec<String>,    // Recognised project file extensions
+}
+
+impl MatchRules {
+    fn from_settings(settings: &Settings) -> Self {
+        let mut builder = globset::GlobSetBuilder::new();
+        for pattern in &settings.exclude_globs {
+            if let Ok(glob) = globset::Glob::new(pattern) {
+                builder.add(glob);
+            }
+        }
+        let exclude_set = builder.build().unwrap_or_else(|_| globset::GlobSet::empty());
+        Self {
+            exclude_set,
+            backup_folder_names: settings.backup_folder_names.clone(),
+            project_extensions: settings.project_extensions.clone(),
+        }
+    }
+
+    fn is_glob_excluded(&self, path: &Path) -> bool {
+        self.exclude_set.is_match(path)
+    }
+
+    fn is_backup_folder(&self, name: &str) -> bool {
+        self.backup_folder_names.iter().any(|n| name.eq_ignore_ascii_case(n))
+    }
+
+    fn is_project_ext(&self, ext: &str) -> bool {
+        self.project_extensions.iter().any(|e| ext.eq_ignore_ascii_case(e))
+    }
+}
+
+// Whether a path matches any user-configured exclusion pattern. Entries
+// containing glob metacharacters (`*`, `?`, `[`) are matched as case-insensitive
+// wildcard patterns (e.g. `*\Temp\*`); everything else keeps the historical
+// case-insensitive substring behaviour. Both path and pattern are normalised to
+// forward slashes so Windows-style separators match either way.
+fn is_excluded(path: &Path, excluded_items: &[String]) -> bool {
+    if excluded_items.is_empty() {
+        return false;
+    }
+    let path_norm = path.to_string_lossy().replace('\\', "/").to_lowercase();
+    excluded_items.iter().any(|pat| {
+        if pat.is_empty() {
+            return false;
+        }
+        if pat.contains('*') || pat.contains('?') || pat.contains('[') {
+            let normalized = pat.replace('\\', "/");
+            match globset::GlobBuilder::new(&normalized)
+                .case_insensitive(true)
+                .literal_separator(false)
+                .build()
+            {
+                Ok(glob) => glob.compile_matcher().is_match(&path_norm),
+                Err(_) => false,
+            }
+        } else {
+            path_norm.contains(&pat.replace('\\', "/").to_lowercase())
+        }
+    })
+}
+
+// Stage 1 of the two-phase scan: a fast count pass that walks a drive with the
+// same pruning rules as the real scan to compute a true file denominator.
+fn count_files(
+    drive: &Path,
+    max_depth: usize,
+    cancelled: &Arc<AtomicBool>,
+    stop_receiver: &crossbeam_channel::Receiver<()>,
+    excluded_items: &[String],
+    rules: &MatchRules,
+) -> usize {
+    let skip_dirs = [
+        "Windows", "Program Files", "Program Files (x86)",
+        "$Recycle.Bin", "System Volume Information", "ProgramData",
+        "AppData", "PerfLogs", "Recovery", "$WINDOWS.~BT", "$WinREAgent",
+        "node_modules", "Config.Msi", "Documents and Settings", ".git", "Intel",
+        "cache", "logs", "temp", "tmp", "obj", "bin", "debug", "release",
+        "build", "dist", "target", "packages"
+    ];
+
+    // Count over exactly the roots and depth the real scan uses: stage 2 walks
+    // each top-level directory at `max_depth`, so counting from the drive root
+    // would traverse a different file set and skew the denominator. Mirror it by
+    // walking the same top-level directories here.
+    let mut count = 0usize;
+    let mut seen = 0usize;
+    for top_dir in get_top_level_directories(drive) {
+        let walker = WalkDir::new(&top_dir)
+            .follow_links(false)
+            .max_depth(max_depth)
+            .into_iter()
+            .filter_entry(|e| {
+                if let Some(file_name) = e.file_name().to_str() {
+                    if skip_dirs.iter().any(|&d| file_name.eq_ignore_ascii_case(d)) {
+                        return false;
+                    }
+                    if file_name.starts_with(".") || file_name.starts_with("~") {
+                        return false;
+                    }
+                }
+                !is_excluded(e.path(), excluded_items) && !rules.is_glob_excluded(e.path())
+            });
+
+        for entry in walker.flatten() {
+            // Honour cancellation during counting too, so stage 1 can be aborted.
+            if seen % 512 == 0 {
+                if stop_receiver.try_recv().is_ok() {
+                    cancelled.store(true, Ordering::Relaxed);
+                }
+                if cancelled.load(Ordering::Relaxed) {
+                    return count;
+                }
+            }
+            seen += 1;
+            if entry.file_type().is_file() {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
 // Scan a directory for FL Studio backups
 fn scan_directory(
     dir: &Path,
@@ -946,6 +1972,11 @@ fn scan_directory(
     total_estimate: Arc<Mutex<usize>>,
     processed_dirs: Arc<Mutex<HashSet<PathBuf>>>,
     max_depth: usize,  // Added max depth parameter
+    cancelled: &Arc<AtomicBool>,  // Shared stop flag observed by every worker
+    stop_receiver: &crossbeam_channel::Receiver<()>,  // UI -> worker stop signal
+    cache: &Arc<Mutex<ScanCache>>,  // Persistent scan-result cache
+    excluded_items: &[String],  // User-configured exclusion patterns
+    rules: &MatchRules,  // Compiled glob/name/extension matching rules
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Skip certain directories that are unlikely to contain FL Studio projects
     let skip_dirs = [
@@ -989,25 +2020,42 @@ fn scan_directory(
                     return false;
                 }
             }
-            true
+            // Prune anything matching a user-configured exclusion pattern or glob.
+            !is_excluded(e.path(), excluded_items) && !rules.is_glob_excluded(e.path())
         });
-    
+
     // Use a time-based update system
     let mut most_recent_update = Instant::now();
-    
+
+    // Check the stop signal every N entries rather than on every single one.
+    const STOP_CHECK_INTERVAL: usize = 128;
+    let mut entries_seen: usize = 0;
+
     // Process entries with better error handling
     for entry_result in walker {
+        // Cooperative cancellation: a set flag (or a freshly received stop signal)
+        // breaks us out of the walk promptly.
+        entries_seen += 1;
+        if entries_seen % STOP_CHECK_INTERVAL == 0 {
+            if stop_receiver.try_recv().is_ok() {
+                cancelled.store(true, Ordering::Relaxed);
+            }
+            if cancelled.load(Ordering::Relaxed) {
+                break;
+            }
+        }
+
         let entry = match entry_result {
             Ok(entry) => entry,
             Err(err) => {
                 // Just skip any errors (permissions, etc) and continue
                 if most_recent_update.elapsed() > Duration::from_secs(5) {
                     most_recent_update = Instant::now();
-                    let _ = tx.send(ScanMessage::Progress(
+                    let _ = tx.send(ScanMessage::Progress(ProgressData::matching(
                         format!("Skipping inaccessible path in {}: {}", dir.display(), err),
                         *global_scanned.lock().unwrap(),
-                        *total_estimate.lock().unwrap()
-                    ));
+                        *total_estimate.lock().unwrap(),
+                    )));
                 }
                 continue;
             }
@@ -1023,91 +2071,122 @@ fn scan_directory(
                 
                 let mut global_count = global_scanned.lock().unwrap();
                 *global_count += 1;
-                
-                // Adjust the total estimate more gradually to prevent jumps
-                let total = {
-                    let mut total = total_estimate.lock().unwrap();
-                    
-                    // If we're approaching 50% of the current estimate, increase it
-                    if *global_count > (*total * 50) / 100 {
-                        // Increase by 50% of current estimate
-                        *total = (*total * 150) / 100;
-                    }
-                    
-                    // Ensure we never show more than 95% until complete
-                    // This prevents the jump from ~40% to 100%
-                    if (*global_count * 100) / *total > 95 && *global_count < *total {
-                        *total = (*global_count * 105) / 100; // Keep at ~95% max
-                    }
-                    
-                    *total
-                };
-                
+
+                // The denominator is now a real count from stage 1, so no fudging.
+                let total = *total_estimate.lock().unwrap();
+
                 // Send progress update (but not too frequently)
                 let now = Instant::now();
-                if now.duration_since(most_recent_update) > Duration::from_millis(500) {
+                // Throttle to a fixed ~100ms cadence so the channel is never flooded.
+                if now.duration_since(most_recent_update) > Duration::from_millis(100) {
                     most_recent_update = now;
-                    let _ = tx.send(ScanMessage::Progress(
+                    let _ = tx.send(ScanMessage::Progress(ProgressData::matching(
                         format!("Scanning {}", dir.display()),
                         *global_count,
-                        total
-                    ));
+                        total,
+                    )));
                 }
             }
         }
         
-        // Fast path: specifically look for "Backup" folders
-        if path.is_dir() && path.file_name().map_or(false, |name| name == "Backup") {
+        // Fast path: look for configured backup folder names (default "Backup").
+        if path.is_dir() && path.file_name().and_then(|n| n.to_str()).map_or(false, |name| rules.is_backup_folder(name)) {
             // Check if parent directory looks like an FL Studio project folder
             if let Some(parent) = path.parent() {
                 // Send update that we found a backup folder
-                let _ = tx.send(ScanMessage::Progress(
+                let _ = tx.send(ScanMessage::Progress(ProgressData::matching(
                     format!("Found FL Studio backup folder: {}", path.display()),
                     *global_scanned.lock().unwrap(),
-                    *total_estimate.lock().unwrap()
-                ));
+                    *total_estimate.lock().unwrap(),
+                )));
                 
-                scan_backup_folder(path, parent, tx, total_found)?;
+                scan_backup_folder(path, parent, tx, total_found, cache, cancelled, rules)?;
             }
         }
     }
-    
+
     Ok(())
 }
 
 // Speed up backup folder scanning
 fn scan_backup_folder(
-    backup_folder: &Path, 
-    project_folder: &Path, 
+    backup_folder: &Path,
+    project_folder: &Path,
     tx: &mpsc::Sender<ScanMessage>,
-    total_found: &mut usize
+    total_found: &mut usize,
+    cache: &Arc<Mutex<ScanCache>>,
+    cancelled: &Arc<AtomicBool>,
+    rules: &MatchRules,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    // Read all .flp files in the backup folder directly - no need for filtering
+    let folder_key = backup_folder.display().to_string();
+    let folder_mtime = fs::metadata(backup_folder).map(|m| modified_secs(&m)).unwrap_or(0);
+
+    // Cache hit: if the folder's mtime is unchanged and every cached backup still
+    // exists at its recorded size, reuse the stored entries without re-reading.
+    {
+        let cache_guard = cache.lock().unwrap();
+        if let Some(cached) = cache_guard.entries.get(&folder_key) {
+            if cached.mtime == folder_mtime && cache_entries_fresh(&cached.backups) {
+                for backup in &cached.backups {
+                    let project_key = format!("{}#{}", project_folder.display(), backup.project_name);
+                    let _ = tx.send(ScanMessage::FoundBackup(project_key, backup.clone()));
+                    *total_found += 1;
+                }
+                return Ok(());
+            }
+        }
+    }
+
+    // Cache miss (or stale): re-read the folder and record what we find.
+    let mut found = Vec::new();
     if let Ok(entries) = fs::read_dir(backup_folder) {
         for entry in entries.filter_map(|e| e.ok()) {
+            // Honour cancellation between entries in the backup folder too.
+            if cancelled.load(Ordering::Relaxed) {
+                break;
+            }
             let path = entry.path();
-            
-            // Only process .flp files
-            if let Some(ext) = path.extension() {
-                if ext != "flp" {
+
+            // Only process recognised project extensions (default "flp").
+            if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+                if !rules.is_project_ext(ext) {
                     continue;
                 }
-                
+
                 if let Some(backup_file) = BackupFile::new(path) {
                     // Use the full project folder path as the key to avoid conflicts
-                    let project_key = format!("{}#{}", 
-                                            project_folder.display(), 
+                    let project_key = format!("{}#{}",
+                                            project_folder.display(),
                                             backup_file.project_name);
-                    
-                    let _ = tx.send(ScanMessage::FoundBackup(project_key, backup_file));
+
+                    let _ = tx.send(ScanMessage::FoundBackup(project_key, backup_file.clone()));
+                    found.push(backup_file);
                     *total_found += 1;
                 }
             }
         }
     }
+
+    cache.lock().unwrap().entries.insert(
+        folder_key,
+        CachedDir {
+            mtime: folder_mtime,
+            project_folder: project_folder.display().to_string(),
+            backups: found,
+        },
+    );
+
     Ok(())
 }
 
+// A cached entry is stale if any recorded file has been deleted, renamed, or
+// resized since it was cached.
+fn cache_entries_fresh(backups: &[BackupFile]) -> bool {
+    backups.iter().all(|b| {
+        fs::metadata(&b.path).map(|m| m.len() == b.file_size).unwrap_or(false)
+    })
+}
+
 // Get settings file path
 fn get_settings_path() -> PathBuf {
     let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
@@ -1230,20 +2309,50 @@ impl eframe::App for FlBackupCleaner {
                         } else {
                             ui.label("Scanning... This may take a while depending on your drive size.");
                         }
+                        // Red Cancel button right next to the spinner; partial
+                        // results gathered so far are kept when it is pressed.
+                        let cancel_btn = egui::Button::new(
+                            egui::RichText::new("â¹ Cancel Scan").size(14.0).strong()
+                        )
+                        .fill(egui::Color32::from_rgb(200, 80, 80));
+                        if ui.add(cancel_btn).clicked() {
+                            self.cancel_scan();
+                        }
                     });
+
+                    // Live tally of backups found so far across all projects.
+                    if !self.found_backups.is_empty() {
+                        ui.add_space(3.0);
+                        let found: usize = self.found_backups.values().map(|v| v.len()).sum();
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{} backups in {} projects so far",
+                                found,
+                                self.found_backups.len()
+                            ))
+                            .size(13.0)
+                            .color(egui::Color32::from_rgb(150, 200, 150)),
+                        );
+                    }
                     
-                    // Enhanced progress bar with color gradient
+                    // Stage indicator for the two-phase scan.
+                    let (stage, max_stage) = self.scan_stage;
+                    if max_stage > 0 {
+                        let label = if stage <= 1 {
+                            format!("Stage {}/{}: Counting files", stage.max(1), max_stage)
+                        } else {
+                            format!("Stage {}/{}: Scanning backups", stage, max_stage)
+                        };
+                        ui.add_space(3.0);
+                        ui.label(egui::RichText::new(label).size(13.0).color(egui::Color32::from_rgb(150, 190, 230)));
+                    }
+
+                    // Enhanced progress bar with color gradient, now backed by a
+                    // real file total from stage 1 rather than a moving estimate.
                     if self.total_files_estimated > 0 {
                         ui.add_space(5.0);
-                        
-                        // Calculate progress with a cap to prevent jumps
-                        let mut progress = (self.files_scanned as f32 / self.total_files_estimated as f32) * 100.0;
-                        
-                        // Cap progress at 95% until scan is complete
-                        if progress > 95.0 && !self.scan_complete {
-                            progress = 95.0;
-                        }
-                        
+
+                        let progress = (self.files_scanned as f32 / self.total_files_estimated as f32 * 100.0).min(100.0);
                         self.progress_percentage = progress;
                         let progress_fraction = progress / 100.0;
                         
@@ -1266,12 +2375,23 @@ impl eframe::App for FlBackupCleaner {
                         // Show time elapsed with better formatting
                         if let Some(start_time) = self.scan_start_time {
                             ui.add_space(2.0);
-                            let elapsed = start_time.elapsed().as_secs();
+                            let elapsed = start_time.elapsed().as_secs_f32();
+                            let elapsed_secs = elapsed as u64;
+                            // Rolling ETA derived from the observed files/sec rate.
+                            let eta_text = if stage >= 2 && self.files_scanned > 0 && elapsed > 0.5 {
+                                let rate = self.files_scanned as f32 / elapsed;
+                                let remaining = self.total_files_estimated.saturating_sub(self.files_scanned);
+                                let eta = (remaining as f32 / rate.max(1.0)) as u64;
+                                format!("  ·  ~{} files/s  ·  ETA {}m {}s", rate as u64, eta / 60, eta % 60)
+                            } else {
+                                String::new()
+                            };
                             ui.label(
                                 egui::RichText::new(format!(
-                                    "Time elapsed: {}m {}s",
-                                    elapsed / 60,
-                                    elapsed % 60
+                                    "Time elapsed: {}m {}s{}",
+                                    elapsed_secs / 60,
+                                    elapsed_secs % 60,
+                                    eta_text
                                 ))
                                 .size(14.0)
                             );
@@ -1379,6 +2499,10 @@ impl eframe::App for FlBackupCleaner {
                     let projects_with_multiple_backups = self.found_backups.values()
                         .filter(|backups| backups.len() > 1)
                         .count();
+                    let broken_backups = self.found_backups.values()
+                        .flatten()
+                        .filter(|b| b.validity.is_broken())
+                        .count();
                     
                     // Enhanced summary card
                     ui.add_space(10.0);
@@ -1402,6 +2526,16 @@ impl eframe::App for FlBackupCleaner {
                                 ui.label(egui::RichText::new("Projects with multiple backups:").strong().color(egui::Color32::from_rgb(200, 200, 200)));
                                 ui.label(egui::RichText::new(format!("{}", projects_with_multiple_backups)).size(28.0).color(egui::Color32::from_rgb(120, 210, 255)));
                                 ui.add_space(5.0);
+
+                                // Fourth row: broken/corrupt backups detected
+                                ui.label(egui::RichText::new("Broken backups:").strong().color(egui::Color32::from_rgb(200, 200, 200)));
+                                let broken_color = if broken_backups > 0 {
+                                    egui::Color32::from_rgb(255, 150, 120)
+                                } else {
+                                    egui::Color32::from_rgb(120, 210, 255)
+                                };
+                                ui.label(egui::RichText::new(format!("{}", broken_backups)).size(28.0).color(broken_color));
+                                ui.add_space(5.0);
                             });
                         });
                     
@@ -1409,14 +2543,33 @@ impl eframe::App for FlBackupCleaner {
                     
                     // Enhanced clean button
                     if projects_with_multiple_backups > 0 {
+                        // Safety toggle right beside the action: when on (the
+                        // default) removed backups go to the OS recycle bin and
+                        // stay recoverable; when off they are deleted for good.
+                        let mut to_trash = self.settings.delete_method != DeleteMethod::PermanentDelete;
+                        if ui.checkbox(&mut to_trash, "Move deleted backups to the recycle bin (recoverable)").changed() {
+                            self.settings.delete_method = if to_trash {
+                                DeleteMethod::MoveToTrash
+                            } else {
+                                DeleteMethod::PermanentDelete
+                            };
+                            let _ = save_settings(&self.settings);
+                        }
+
+                        let policy_label = match self.settings.retention {
+                            RetentionPolicy::KeepLatest => "Keep Latest Only".to_string(),
+                            RetentionPolicy::KeepN(n) => format!("Keep {} Most Recent", n),
+                            RetentionPolicy::KeepNewerThan(d) => format!("Keep Newer Than {} Days", d),
+                            RetentionPolicy::MaxTotalMb(m) => format!("Cap at {} MB", m),
+                        };
                         let clean_btn = egui::Button::new(
-                            egui::RichText::new("ðŸ§¹ Clean Old Backups (Keep Latest Only)")
+                            egui::RichText::new(format!("ðŸ§¹ Clean Old Backups ({})", policy_label))
                                 .size(18.0)
                                 .strong()
                         )
                         .min_size(egui::vec2(320.0, 40.0))
                         .fill(egui::Color32::from_rgb(80, 170, 80));
-                        
+
                         if ui.add(clean_btn).clicked() {
                             self.clean_backups();
                         }
@@ -1438,6 +2591,13 @@ impl eframe::App for FlBackupCleaner {
                                     ui.vertical_centered(|ui| {
                                         ui.label(egui::RichText::new("Space Saved").strong().color(egui::Color32::from_rgb(200, 255, 200)));
                                         ui.label(egui::RichText::new(format!("{:.2} MB", size_mb)).size(32.0).color(egui::Color32::from_rgb(120, 255, 120)));
+                                        // Note whether the freed files are still recoverable.
+                                        let recoverability = match self.settings.delete_method {
+                                            DeleteMethod::MoveToTrash => "Files moved to the recycle bin (recoverable)",
+                                            DeleteMethod::PermanentDelete => "Files permanently removed (not recoverable)",
+                                            DeleteMethod::DryRun => "Dry run — no files were removed",
+                                        };
+                                        ui.label(egui::RichText::new(recoverability).size(13.0).italics().color(egui::Color32::from_rgb(180, 220, 180)));
                                     });
                                 });
                         }
@@ -1445,13 +2605,127 @@ impl eframe::App for FlBackupCleaner {
                     
                     ui.add_space(15.0);
                     
+                    // Size-breakdown visualization: one proportional bar per
+                    // project, with the portion reclaimable under the current
+                    // retention policy highlighted.
+                    {
+                        // Total and reclaimable bytes per project, largest first.
+                        let mut rows: Vec<(String, u64, u64)> = self
+                            .found_backups
+                            .iter()
+                            .map(|(key, backups)| {
+                                let total: u64 = backups.iter().map(|b| b.file_size).sum();
+                                let mut ordered = backups.clone();
+                                ordered.sort_by(|a, b| b.modified_date.cmp(&a.modified_date));
+                                let keep = if ordered.len() > 1 {
+                                    retained_count(&ordered, self.settings.retention)
+                                } else {
+                                    ordered.len()
+                                };
+                                let freed: u64 = ordered.iter().skip(keep).map(|b| b.file_size).sum();
+                                let name = key.split('#').last().unwrap_or("Unknown").to_string();
+                                (name, total, freed)
+                            })
+                            .filter(|(_, total, _)| *total > 0)
+                            .collect();
+                        rows.sort_by(|a, b| b.1.cmp(&a.1));
+                        rows.truncate(15); // Keep the chart legible.
+
+                        if !rows.is_empty() {
+                            ui.add_space(10.0);
+                            ui.heading(egui::RichText::new("Storage Breakdown:").size(18.0).strong());
+                            ui.label(
+                                egui::RichText::new("Bar width is total backup size per project; the orange portion is freed by the current policy.")
+                                    .size(12.0)
+                                    .color(egui::Color32::from_rgb(170, 170, 170)),
+                            );
+                            let max_total = rows.iter().map(|r| r.1).max().unwrap_or(1).max(1);
+                            let full_width = ui.available_width().min(520.0);
+                            for (name, total, freed) in &rows {
+                                let row_w = full_width * (*total as f32 / max_total as f32);
+                                let (rect, _resp) = ui.allocate_exact_size(
+                                    egui::vec2(full_width, 22.0),
+                                    egui::Sense::hover(),
+                                );
+                                let painter = ui.painter_at(rect);
+                                // Kept portion (blue) sits behind the freed portion (orange).
+                                let kept_color = egui::Color32::from_rgb(90, 150, 220);
+                                let freed_color = egui::Color32::from_rgb(230, 150, 60);
+                                let bar = egui::Rect::from_min_size(rect.min, egui::vec2(row_w, rect.height()));
+                                painter.rect_filled(bar, 3.0, kept_color);
+                                let freed_w = row_w * (*freed as f32 / (*total as f32).max(1.0));
+                                let freed_rect = egui::Rect::from_min_size(
+                                    egui::pos2(bar.max.x - freed_w, bar.min.y),
+                                    egui::vec2(freed_w, bar.height()),
+                                );
+                                painter.rect_filled(freed_rect, 3.0, freed_color);
+                                painter.text(
+                                    egui::pos2(rect.min.x + 6.0, rect.center().y),
+                                    egui::Align2::LEFT_CENTER,
+                                    format!("{}  —  {} (frees {})", name, humanize_bytes(*total), humanize_bytes(*freed)),
+                                    egui::FontId::proportional(12.0),
+                                    egui::Color32::WHITE,
+                                );
+                            }
+                        }
+                    }
+
+                    ui.add_space(15.0);
+
                     // Enhanced project details display
                     ui.heading(egui::RichText::new("Project Details:").size(18.0).strong());
+
+                    // Filter box and sort selector over the found projects.
+                    ui.horizontal(|ui| {
+                        ui.label("Filter:");
+                        ui.text_edit_singleline(&mut self.project_filter);
+                        if ui.small_button("âœ–").clicked() {
+                            self.project_filter.clear();
+                        }
+                        ui.separator();
+                        ui.label("Sort by:");
+                        egui::ComboBox::from_id_source("project_sort")
+                            .selected_text(match self.project_sort {
+                                ProjectSort::TotalSize => "Total size",
+                                ProjectSort::BackupCount => "Backup count",
+                                ProjectSort::MostRecent => "Most recent",
+                            })
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut self.project_sort, ProjectSort::TotalSize, "Total size");
+                                ui.selectable_value(&mut self.project_sort, ProjectSort::BackupCount, "Backup count");
+                                ui.selectable_value(&mut self.project_sort, ProjectSort::MostRecent, "Most recent");
+                            });
+                    });
+
+                    // Build the display order: case-insensitive substring filter over
+                    // project name or path, then sort by the selected key.
+                    let filter = self.project_filter.trim().to_lowercase();
+                    let mut ordered_projects: Vec<(&String, &Vec<BackupFile>)> = self
+                        .found_backups
+                        .iter()
+                        .filter(|(key, _)| filter.is_empty() || key.to_lowercase().contains(&filter))
+                        .collect();
+                    match self.project_sort {
+                        ProjectSort::TotalSize => ordered_projects.sort_by(|a, b| {
+                            let sa: u64 = a.1.iter().map(|x| x.file_size).sum();
+                            let sb: u64 = b.1.iter().map(|x| x.file_size).sum();
+                            sb.cmp(&sa)
+                        }),
+                        ProjectSort::BackupCount => {
+                            ordered_projects.sort_by(|a, b| b.1.len().cmp(&a.1.len()))
+                        }
+                        ProjectSort::MostRecent => ordered_projects.sort_by(|a, b| {
+                            let ra = a.1.iter().map(|x| x.modified_date).max().unwrap_or(0);
+                            let rb = b.1.iter().map(|x| x.modified_date).max().unwrap_or(0);
+                            rb.cmp(&ra)
+                        }),
+                    }
+
                     egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
-                        for (project_key, backups) in &self.found_backups {
+                        for (project_key, backups) in ordered_projects {
                             let project_name = project_key.split('#').last().unwrap_or("Unknown");
                             let project_path = project_key.split('#').next().unwrap_or("Unknown path");
-                            
+
                             egui::Frame::group(ui.style())
                                 .fill(egui::Color32::from_rgb(35, 40, 50))
                                 .show(ui, |ui| {
@@ -1459,18 +2733,49 @@ impl eframe::App for FlBackupCleaner {
                                     ui.label(egui::RichText::new(format!("Project: {}", project_name)).strong().size(16.0).color(egui::Color32::from_rgb(150, 200, 255)));
                                     ui.label(egui::RichText::new(format!("Path: {}", project_path)).size(13.0).color(egui::Color32::from_rgb(180, 180, 180)));
                                     ui.label(egui::RichText::new(format!("Backups: {}", backups.len())).color(egui::Color32::from_rgb(200, 200, 200)));
-                                    
+
                                     // Add some space before backups
                                     ui.add_space(5.0);
-                                    
+
+                                    // Preview the active retention policy: sort a copy
+                                    // latest-first and mark everything past the kept
+                                    // window as "would be removed" before the user cleans.
+                                    let mut ordered: Vec<BackupFile> = backups.clone();
+                                    ordered.sort_by(|a, b| b.modified_date.cmp(&a.modified_date));
+
+                                    // Digests that appear more than once are exact
+                                    // byte-identical duplicates within this project.
+                                    let mut digest_counts: HashMap<&str, usize> = HashMap::new();
+                                    for b in &ordered {
+                                        if let Some(d) = &b.digest {
+                                            *digest_counts.entry(d.as_str()).or_insert(0) += 1;
+                                        }
+                                    }
+                                    let keep = if ordered.len() > 1 {
+                                        retained_count(&ordered, self.settings.retention)
+                                    } else {
+                                        ordered.len()
+                                    };
+
                                     // Display backups with indentation
-                                    for backup in backups {
+                                    for (i, backup) in ordered.iter().enumerate() {
                                         ui.horizontal(|ui| {
                                             ui.add_space(20.0); // Indentation
                                             let size_kb = backup.file_size as f64 / 1024.0;
+                                            let will_remove = i >= keep || (self.settings.delete_broken && backup.validity.is_broken());
+                                            let (marker, color) = if will_remove {
+                                                ("âœ– would remove", egui::Color32::from_rgb(255, 150, 120))
+                                            } else {
+                                                ("âœ“ keep", egui::Color32::from_rgb(150, 220, 150))
+                                            };
+                                            let is_duplicate = backup
+                                                .digest
+                                                .as_deref()
+                                                .map_or(false, |d| digest_counts.get(d).copied().unwrap_or(0) > 1);
+                                            let dup_tag = if is_duplicate { "  âŽ˜ exact duplicate" } else { "" };
                                             ui.label(
-                                                egui::RichText::new(format!("â””â”€ {} ({:.1} KB)", backup.timestamp, size_kb))
-                                                    .color(egui::Color32::from_rgb(220, 220, 220))
+                                                egui::RichText::new(format!("â””â”€ {} ({:.1} KB)  {}{}", backup.timestamp, size_kb, marker, dup_tag))
+                                                    .color(color)
                                             );
                                         });
                                     }
@@ -1494,7 +2799,219 @@ impl eframe::App for FlBackupCleaner {
     }
 }
 
+// Run a scan synchronously over the given roots and collect the results,
+// reusing the same traversal and matching logic as the GUI. Intended for the
+// headless CLI, where there is no frame loop to drain a channel.
+// Returns the found backups alongside a flag indicating whether any root failed
+// to scan, so the headless CLI can exit non-zero on errors.
+fn scan_roots_blocking(
+    roots: &[PathBuf],
+    settings: &Settings,
+) -> (HashMap<String, Vec<BackupFile>>, bool) {
+    let (tx, rx) = mpsc::channel();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let (_stop_tx, stop_rx) = crossbeam_channel::unbounded();
+    let cache = Arc::new(Mutex::new(load_cache()));
+    let rules = MatchRules::from_settings(settings);
+    let drive_scanned = Arc::new(Mutex::new(0usize));
+    let global_scanned = Arc::new(Mutex::new(0usize));
+    let total_estimate = Arc::new(Mutex::new(0usize));
+    let processed_dirs = Arc::new(Mutex::new(HashSet::new()));
+
+    let mut excluded_items = settings.excluded_items.clone();
+    excluded_items.extend(
+        settings
+            .excluded_directories
+            .iter()
+            .map(|p| p.display().to_string()),
+    );
+
+    let mut had_error = false;
+    for root in roots {
+        let mut total_found = 0usize;
+        if let Err(e) = scan_directory(
+            root,
+            &tx,
+            &mut total_found,
+            drive_scanned.clone(),
+            global_scanned.clone(),
+            total_estimate.clone(),
+            processed_dirs.clone(),
+            settings.scan_depth,
+            &cancelled,
+            &stop_rx,
+            &cache,
+            &excluded_items,
+            &rules,
+        ) {
+            eprintln!("Error scanning {}: {}", root.display(), e);
+            had_error = true;
+        }
+    }
+    drop(tx);
+
+    if let Ok(guard) = cache.lock() {
+        let _ = save_cache(&guard);
+    }
+
+    let mut found: HashMap<String, Vec<BackupFile>> = HashMap::new();
+    for message in rx {
+        if let ScanMessage::FoundBackup(project_key, backup_file) = message {
+            found.entry(project_key).or_default().push(backup_file);
+        }
+    }
+    compute_duplicate_digests(&mut found);
+    (found, had_error)
+}
+
+#[derive(clap::Parser)]
+#[command(name = "flcleaner", about = "Find and clean FL Studio project backups")]
+struct Cli {
+    #[command(subcommand)]
+    command: CliCommand,
+}
+
+#[derive(clap::Subcommand)]
+enum CliCommand {
+    /// Scan for backups and print a per-project summary
+    Scan {
+        /// Drive or folder to scan (repeatable); defaults to all drives
+        #[arg(long = "drive")]
+        drives: Vec<PathBuf>,
+    },
+    /// Scan and then remove old backups according to the retention policy
+    Clean {
+        /// Drive or folder to scan (repeatable); defaults to all drives
+        #[arg(long = "drive")]
+        drives: Vec<PathBuf>,
+        /// Keep only the single newest backup per project
+        #[arg(long = "keep-latest")]
+        keep_latest: bool,
+        /// Keep the N most recent backups per project
+        #[arg(long = "keep")]
+        keep: Option<usize>,
+        /// Report what would be removed without touching any files
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+        /// Permanently delete instead of moving to the recycle bin
+        #[arg(long = "permanent")]
+        permanent: bool,
+    },
+}
+
+// Resolve scan roots from CLI drive/folder arguments, falling back to every
+// mounted drive when none were supplied.
+fn cli_roots(drives: &[PathBuf]) -> Vec<PathBuf> {
+    if drives.is_empty() {
+        get_all_drives().into_iter().map(|d| d.path).collect()
+    } else {
+        drives.to_vec()
+    }
+}
+
+// Print the per-project summary shared by both CLI subcommands.
+fn print_summary(found: &HashMap<String, Vec<BackupFile>>) {
+    let mut total_files = 0usize;
+    let mut total_bytes = 0u64;
+    for (project, backups) in found {
+        let bytes: u64 = backups.iter().map(|b| b.file_size).sum();
+        total_files += backups.len();
+        total_bytes += bytes;
+        println!("{}: {} backups ({})", project, backups.len(), humanize_bytes(bytes));
+    }
+    println!(
+        "Total: {} backups across {} projects ({})",
+        total_files,
+        found.len(),
+        humanize_bytes(total_bytes)
+    );
+}
+
+// Headless entry point. Returns a process exit code.
+fn run_cli(cli: Cli) -> i32 {
+    let settings = load_settings().unwrap_or_default();
+    match cli.command {
+        CliCommand::Scan { drives } => {
+            let roots = cli_roots(&drives);
+            let (found, had_error) = scan_roots_blocking(&roots, &settings);
+            print_summary(&found);
+            if had_error {
+                1
+            } else {
+                0
+            }
+        }
+        CliCommand::Clean {
+            drives,
+            keep_latest,
+            keep,
+            dry_run,
+            permanent,
+        } => {
+            let roots = cli_roots(&drives);
+            let (mut found, had_error) = scan_roots_blocking(&roots, &settings);
+            print_summary(&found);
+
+            let retention = if let Some(n) = keep {
+                RetentionPolicy::KeepN(n)
+            } else if keep_latest {
+                RetentionPolicy::KeepLatest
+            } else {
+                settings.retention
+            };
+            let method = if dry_run {
+                DeleteMethod::DryRun
+            } else if permanent {
+                DeleteMethod::PermanentDelete
+            } else {
+                DeleteMethod::MoveToTrash
+            };
+
+            let (deleted, bytes, failed) =
+                clean_found_backups(&mut found, method, settings.delete_broken, retention);
+            match method {
+                DeleteMethod::DryRun => println!(
+                    "Dry run: {} files ({}) would be removed.",
+                    deleted,
+                    humanize_bytes(bytes)
+                ),
+                DeleteMethod::MoveToTrash => println!(
+                    "Moved {} files ({}) to the recycle bin.",
+                    deleted,
+                    humanize_bytes(bytes)
+                ),
+                DeleteMethod::PermanentDelete => println!(
+                    "Deleted {} files, reclaimed {}.",
+                    deleted,
+                    humanize_bytes(bytes)
+                ),
+            }
+            if failed > 0 {
+                eprintln!("{} file(s) could not be removed.", failed);
+            }
+            if had_error || failed > 0 {
+                1
+            } else {
+                0
+            }
+        }
+    }
+}
+
 fn main() -> Result<(), eframe::Error> {
+    // When invoked with arguments, run headlessly; otherwise launch the GUI.
+    if std::env::args_os().count() > 1 {
+        use clap::Parser;
+        match Cli::try_parse() {
+            Ok(cli) => std::process::exit(run_cli(cli)),
+            Err(e) => {
+                // clap prints help/usage; mirror its intended exit code.
+                e.print().ok();
+                std::process::exit(if e.use_stderr() { 2 } else { 0 });
+            }
+        }
+    }
+
     let app = FlBackupCleaner::new();
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()